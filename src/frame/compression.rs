@@ -0,0 +1,101 @@
+// Copyright (C) 2025  Krzysztof Molski
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::io::{Read, Write};
+
+use flate2::read::{DeflateDecoder, GzDecoder};
+use flate2::write::{DeflateEncoder, GzEncoder};
+use flate2::Compression;
+
+use crate::frame::{StompFrameError, CONTENT_ENCODING};
+
+/// Body compression codecs that can be named by a STOMP `content-encoding` header.
+///
+/// This is deliberately a closed enum rather than a trait object: adding a new codec (e.g.
+/// brotli) only requires a new variant and match arm here.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(super) enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Looks up the codec named by a `content-encoding` header value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StompFrameError::HeaderError`] if `name` does not name a supported codec.
+    pub(super) fn parse(name: &str) -> Result<Self, StompFrameError> {
+        match name {
+            "gzip" => Ok(Self::Gzip),
+            "deflate" => Ok(Self::Deflate),
+            other => Err(StompFrameError::HeaderError(
+                CONTENT_ENCODING.into(),
+                other.to_string(),
+            )),
+        }
+    }
+
+    /// Compresses `body` with this codec.
+    pub(super) fn compress(self, body: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body).expect("writes to a Vec<u8> cannot fail");
+                encoder.finish().expect("writes to a Vec<u8> cannot fail")
+            }
+            Self::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body).expect("writes to a Vec<u8> cannot fail");
+                encoder.finish().expect("writes to a Vec<u8> cannot fail")
+            }
+        }
+    }
+
+    /// Decompresses `body` with this codec.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StompFrameError::HeaderError`] if `body` is not valid compressed data.
+    pub(super) fn decompress(self, body: &[u8]) -> Result<Vec<u8>, StompFrameError> {
+        let mut decompressed = Vec::new();
+        let result = match self {
+            Self::Gzip => GzDecoder::new(body).read_to_end(&mut decompressed),
+            Self::Deflate => DeflateDecoder::new(body).read_to_end(&mut decompressed),
+        };
+        result.map(|_| decompressed).map_err(|err| {
+            StompFrameError::HeaderError(CONTENT_ENCODING.into(), err.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gzip_round_trip_preserves_content() {
+        let compressed = ContentEncoding::Gzip.compress(b"hello, world!");
+        let decompressed = ContentEncoding::Gzip.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, b"hello, world!");
+    }
+
+    #[test]
+    fn deflate_round_trip_preserves_content() {
+        let compressed = ContentEncoding::Deflate.compress(b"hello, world!");
+        let decompressed = ContentEncoding::Deflate.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, b"hello, world!");
+    }
+
+    #[test]
+    fn parse_unknown_codec_returns_error() {
+        let result = ContentEncoding::parse("brotli");
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+
+    #[test]
+    fn decompress_invalid_data_returns_error() {
+        let result = ContentEncoding::Gzip.decompress(b"not gzip data");
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2025  Krzysztof Molski
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use thiserror::Error;
+
+use crate::frame::parser::{self, FrameParse};
+use crate::frame::{StompFrame, StompFrameError};
+
+/// Error returned by [`StompDecoder::decode`] when the buffered bytes don't form a valid frame.
+///
+/// Carries the frames that were already successfully decoded earlier in the same call, so a
+/// trailing malformed frame doesn't cause them to be silently dropped.
+#[derive(Error, Debug)]
+#[error("{source}")]
+pub struct StompDecodeError {
+    pub frames: Vec<StompFrame>,
+    #[source]
+    pub source: StompFrameError,
+}
+
+/// Incremental decoder that reassembles [`StompFrame`]s out of a stream of WebSocket payloads.
+///
+/// A single WebSocket message may contain a partial frame, several frames back-to-back, or lone
+/// heartbeat newlines between frames. `StompDecoder` buffers whatever [`Self::decode`] is given
+/// and only discards bytes once they are lone heartbeat newlines or make up a frame it has fully
+/// parsed, so a frame split across two calls is reassembled correctly.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StompDecoder {
+    buffer: Vec<u8>,
+}
+
+impl StompDecoder {
+    /// Creates an empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk of bytes to the decoder and returns every frame that could be fully parsed
+    /// out of the buffer so far. Bytes left over from an incomplete trailing frame are retained
+    /// for the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StompDecodeError`] as soon as the buffered bytes are found not to form a
+    /// valid frame, carrying the frames successfully decoded before the failure so that earlier,
+    /// legitimate frames in the same chunk are not silently dropped. The decoder should not be
+    /// reused afterwards.
+    pub fn decode(&mut self, chunk: &[u8]) -> Result<Vec<StompFrame>, StompDecodeError> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut frames = Vec::new();
+        loop {
+            let Ok((_, skipped)) = parser::skip_heartbeats(&self.buffer) else {
+                break; // not enough bytes yet to know whether more heartbeats follow
+            };
+            if skipped > 0 {
+                self.buffer.drain(..skipped);
+            }
+
+            match parser::parse_frame_streaming(&self.buffer) {
+                Ok(FrameParse::Frame(cmd, headers, body, consumed)) => {
+                    match StompFrame::from_owned_body(cmd, headers, body) {
+                        Ok(frame) => {
+                            self.buffer.drain(..consumed);
+                            frames.push(frame);
+                        }
+                        Err(source) => return Err(StompDecodeError { frames, source }),
+                    }
+                }
+                Ok(FrameParse::Incomplete) => break,
+                Err(source) => return Err(StompDecodeError { frames, source }),
+            }
+        }
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::frame::StompCommand;
+
+    use super::*;
+
+    #[test]
+    fn decode_single_frame_returns_it_whole() {
+        let mut decoder = StompDecoder::new();
+        let frames = decoder.decode(b"CONNECT\n\n\0").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].cmd, StompCommand::CONNECT);
+    }
+
+    #[test]
+    fn decode_frame_split_across_calls_is_reassembled() {
+        let mut decoder = StompDecoder::new();
+        assert_eq!(decoder.decode(b"SEND\ndestination").unwrap(), Vec::new());
+        assert_eq!(decoder.decode(b":/q\n\n").unwrap(), Vec::new());
+        let frames = decoder.decode(b"body\0").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].cmd, StompCommand::SEND);
+        assert_eq!(
+            frames[0].headers,
+            HashMap::from([("destination".to_string(), "/q".to_string())])
+        );
+        assert_eq!(frames[0].body, Some(b"body".to_vec()));
+    }
+
+    #[test]
+    fn decode_batched_frames_returns_all_of_them() {
+        let mut decoder = StompDecoder::new();
+        let frames = decoder
+            .decode(b"CONNECT\n\n\0DISCONNECT\n\n\0")
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].cmd, StompCommand::CONNECT);
+        assert_eq!(frames[1].cmd, StompCommand::DISCONNECT);
+    }
+
+    #[test]
+    fn decode_skips_heartbeats_between_frames() {
+        let mut decoder = StompDecoder::new();
+        let frames = decoder.decode(b"\n\r\nCONNECT\n\n\0\n").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].cmd, StompCommand::CONNECT);
+    }
+
+    #[test]
+    fn decode_invalid_frame_returns_error() {
+        let mut decoder = StompDecoder::new();
+        let result = decoder.decode(b"nonsense\n\n\0");
+        match result {
+            Err(StompDecodeError {
+                frames,
+                source: StompFrameError::SyntaxError(..),
+            }) => assert!(frames.is_empty()),
+            other => panic!("expected a syntax error with no decoded frames, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decode_valid_frame_then_malformed_frame_keeps_valid_one() {
+        let mut decoder = StompDecoder::new();
+        let result = decoder.decode(b"CONNECT\n\n\0garbage");
+        match result {
+            Err(StompDecodeError {
+                frames,
+                source: StompFrameError::SyntaxError(..),
+            }) => {
+                assert_eq!(frames.len(), 1);
+                assert_eq!(frames[0].cmd, StompCommand::CONNECT);
+            }
+            other => panic!("expected the CONNECT frame alongside the error, got {other:?}"),
+        }
+    }
+}
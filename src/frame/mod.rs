@@ -1,10 +1,17 @@
+mod decoder;
+#[cfg(feature = "compression")]
+mod compression;
 mod parser;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::str::Utf8Error;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use thiserror::Error;
 
+pub use decoder::{StompDecodeError, StompDecoder};
 use parser::stomp_command_parse_impl;
 
 macro_rules! stomp_command_impl {
@@ -79,6 +86,8 @@ pub enum StompFrameError {
     HeaderError(String, String),
     #[error("syntax error at: {0}")]
     SyntaxError(String),
+    #[error("invalid JSON body: {0}")]
+    JsonError(#[from] serde_json::Error),
 }
 
 impl StompFrame {
@@ -87,11 +96,18 @@ impl StompFrame {
         headers: HashMap<String, String>,
         body: impl AsRef<[u8]>,
     ) -> Result<Self, StompFrameError> {
-        let body = if body.as_ref().is_empty() {
-            None
-        } else {
-            Some(body.as_ref().to_vec())
-        };
+        Self::from_owned_body(cmd, headers, body.as_ref().to_vec())
+    }
+
+    /// Like [`Self::new`], but takes an already-owned body buffer instead of cloning one out of
+    /// a borrow, so the parser (which already owns the decompressed wire bytes) doesn't pay for
+    /// a second clone on top of its own.
+    pub(super) fn from_owned_body(
+        cmd: StompCommand,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    ) -> Result<Self, StompFrameError> {
+        let body = if body.is_empty() { None } else { Some(body) };
         if !cmd.may_have_body() && body.is_some() {
             return Err(StompFrameError::SyntaxError(format!(
                 "frame type {cmd:?} must not have a body"
@@ -99,26 +115,132 @@ impl StompFrame {
         }
         Ok(StompFrame { cmd, headers, body })
     }
+
+    /// Builds a frame whose body is `value` serialized as JSON, setting `content-type` to
+    /// `application/json` so that [`Self::body_as`] can later recover it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StompFrameError::JsonError`] if `value` cannot be serialized, or any error
+    /// [`Self::new`] can return.
+    pub fn with_json<T: Serialize>(
+        cmd: StompCommand,
+        mut headers: HashMap<String, String>,
+        value: &T,
+    ) -> Result<Self, StompFrameError> {
+        let body = serde_json::to_vec(value)?;
+        headers.insert(CONTENT_TYPE.to_string(), APPLICATION_JSON.to_string());
+        Self::new(cmd, headers, body)
+    }
+
+    /// Deserializes the frame body according to its `content-type` header.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StompFrameError::HeaderError`] if `content-type` is missing or names an
+    /// unsupported media type, or a [`StompFrameError::JsonError`] if the body does not match
+    /// `T`.
+    pub fn body_as<T: DeserializeOwned>(&self) -> Result<T, StompFrameError> {
+        match self.headers.get(CONTENT_TYPE).map(String::as_str) {
+            Some(APPLICATION_JSON) => {
+                let body = self.body.as_deref().unwrap_or_default();
+                Ok(serde_json::from_slice(body)?)
+            }
+            other => Err(StompFrameError::HeaderError(
+                CONTENT_TYPE.into(),
+                other.unwrap_or_default().to_string(),
+            )),
+        }
+    }
 }
 
-impl From<&StompFrame> for Vec<u8> {
-    fn from(frame: &StompFrame) -> Self {
+impl TryFrom<&StompFrame> for Vec<u8> {
+    type Error = StompFrameError;
+
+    fn try_from(frame: &StompFrame) -> Result<Self, Self::Error> {
         let mut serialized = Vec::new();
         let cmd: &str = frame.cmd.into();
         serialized.extend_from_slice(cmd.as_bytes());
         serialized.push(b'\n');
-        for (key, value) in &frame.headers {
+        let (headers, body) = encode_body(&frame.headers, frame.body.as_deref())?;
+        for (key, value) in headers.iter() {
             serialized.extend_from_slice(escape_header(key, frame.cmd).as_bytes());
             serialized.push(HEADER_SEP);
             serialized.extend_from_slice(escape_header(value, frame.cmd).as_bytes());
             serialized.push(b'\n');
         }
         serialized.push(b'\n');
-        if let Some(body) = &frame.body {
+        if let Some(body) = &body {
             serialized.extend_from_slice(body);
         }
         serialized.push(b'\0');
-        serialized
+        Ok(serialized)
+    }
+}
+
+/// Compresses `body` per the `content-encoding` header, if one is present, patching
+/// `content-length` to match. Returns `headers`/`body` unchanged if no `content-encoding` header
+/// is set.
+///
+/// # Errors
+///
+/// Returns a [`StompFrameError::HeaderError`] if `content-encoding` names a codec this build
+/// doesn't support (either because it's unknown, or because the crate was built without the
+/// `compression` feature).
+fn encode_body<'a>(
+    headers: &'a HashMap<String, String>,
+    body: Option<&'a [u8]>,
+) -> Result<(Cow<'a, HashMap<String, String>>, Option<Cow<'a, [u8]>>), StompFrameError> {
+    let Some(encoding) = headers.get(CONTENT_ENCODING) else {
+        return Ok((Cow::Borrowed(headers), body.map(Cow::Borrowed)));
+    };
+    #[cfg(feature = "compression")]
+    {
+        let encoding = compression::ContentEncoding::parse(encoding)?;
+        let compressed = encoding.compress(body.unwrap_or_default());
+        let mut headers = headers.clone();
+        headers.insert(CONTENT_LENGTH.to_string(), compressed.len().to_string());
+        Ok((Cow::Owned(headers), Some(Cow::Owned(compressed))))
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(StompFrameError::HeaderError(
+            CONTENT_ENCODING.into(),
+            encoding.clone(),
+        ))
+    }
+}
+
+/// Decompresses `body` per the `content-encoding` header, if one is present, patching
+/// `content-length` to the decompressed size so it stays consistent with the in-memory body.
+/// Returns `body` unchanged if no `content-encoding` header is set. Only the parse path
+/// (`parser::parse_frame`/`parse_frame_streaming`) should call this, since `body` here must be
+/// the raw wire bytes, not a plaintext body a caller is about to send.
+///
+/// # Errors
+///
+/// Returns a [`StompFrameError::HeaderError`] if `content-encoding` names a codec this build
+/// doesn't support (either because it's unknown, or because the crate was built without the
+/// `compression` feature).
+pub(super) fn decode_body(
+    headers: &mut HashMap<String, String>,
+    body: Vec<u8>,
+) -> Result<Vec<u8>, StompFrameError> {
+    let Some(encoding) = headers.get(CONTENT_ENCODING) else {
+        return Ok(body);
+    };
+    #[cfg(feature = "compression")]
+    {
+        let decompressed = compression::ContentEncoding::parse(encoding)?.decompress(&body)?;
+        headers.insert(CONTENT_LENGTH.to_string(), decompressed.len().to_string());
+        Ok(decompressed)
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(StompFrameError::HeaderError(
+            CONTENT_ENCODING.into(),
+            encoding.clone(),
+        ))
     }
 }
 
@@ -142,6 +264,9 @@ fn escape_header(header: &str, cmd: StompCommand) -> String {
 
 const HEADER_SEP: u8 = b':';
 const CONTENT_LENGTH: &str = "content-length";
+const CONTENT_TYPE: &str = "content-type";
+const APPLICATION_JSON: &str = "application/json";
+const CONTENT_ENCODING: &str = "content-encoding";
 const DESTINATION: &str = "destination";
 const RECEIPT: &str = "receipt";
 
@@ -157,7 +282,7 @@ mod tests {
             b"body",
         )
         .unwrap();
-        let serialized: Vec<u8> = (&frame).into();
+        let serialized: Vec<u8> = (&frame).try_into().unwrap();
         let deserialized = StompFrame::try_from(serialized.as_slice()).unwrap();
         assert_eq!(frame, deserialized);
     }
@@ -170,8 +295,108 @@ mod tests {
             b"",
         )
         .unwrap();
-        let serialized: Vec<u8> = (&frame).into();
+        let serialized: Vec<u8> = (&frame).try_into().unwrap();
         let deserialized = StompFrame::try_from(serialized.as_slice()).unwrap();
         assert_eq!(frame, deserialized);
     }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn gzip_content_encoding_round_trips_through_wire_bytes() {
+        // `frame` holds a plaintext body; serializing it compresses that body onto the wire, and
+        // parsing the wire bytes back decompresses it, so the body matches but `content-length`
+        // is wire-relative (compressed going out, decompressed coming back) rather than identical.
+        let frame = StompFrame::new(
+            StompCommand::SEND,
+            HashMap::from([("content-encoding".to_string(), "gzip".to_string())]),
+            b"body",
+        )
+        .unwrap();
+        let serialized: Vec<u8> = (&frame).try_into().unwrap();
+        let deserialized = StompFrame::try_from(serialized.as_slice()).unwrap();
+        assert_eq!(deserialized.cmd, frame.cmd);
+        assert_eq!(deserialized.body, frame.body);
+        assert_eq!(
+            deserialized.headers.get("content-encoding"),
+            Some(&"gzip".to_string())
+        );
+        assert_eq!(
+            deserialized.headers.get("content-length"),
+            Some(&frame.body.as_ref().unwrap().len().to_string())
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn from_unsupported_content_encoding_returns_error() {
+        let frame = b"SEND\n\
+                      content-encoding:brotli\n\
+                      \n\
+                      body\0";
+        let result = StompFrame::try_from(&frame[..]);
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn from_content_encoding_without_compression_feature_returns_error() {
+        let frame = b"SEND\n\
+                      content-encoding:gzip\n\
+                      \n\
+                      body\0";
+        let result = StompFrame::try_from(&frame[..]);
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn into_with_content_encoding_without_compression_feature_returns_error() {
+        let frame = StompFrame::new(
+            StompCommand::SEND,
+            HashMap::from([("content-encoding".to_string(), "gzip".to_string())]),
+            b"body",
+        )
+        .unwrap();
+        let result: Result<Vec<u8>, _> = (&frame).try_into();
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
+    struct Payload {
+        id: u32,
+        name: String,
+    }
+
+    #[test]
+    fn with_json_round_trips_through_body_as() {
+        let value = Payload {
+            id: 1,
+            name: "stomp".to_string(),
+        };
+        let frame = StompFrame::with_json(StompCommand::SEND, HashMap::new(), &value).unwrap();
+        assert_eq!(
+            frame.headers.get("content-type"),
+            Some(&"application/json".to_string())
+        );
+        let deserialized: Payload = frame.body_as().unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    fn body_as_without_content_type_returns_error() {
+        let frame = StompFrame::new(StompCommand::SEND, HashMap::new(), b"{}").unwrap();
+        let result = frame.body_as::<Payload>();
+        assert!(matches!(result, Err(StompFrameError::HeaderError(..))));
+    }
+
+    #[test]
+    fn body_as_with_mismatched_json_type_returns_error() {
+        let frame = StompFrame::with_json(StompCommand::SEND, HashMap::new(), &Payload {
+            id: 1,
+            name: "stomp".to_string(),
+        })
+        .unwrap();
+        let result = frame.body_as::<Vec<u32>>();
+        assert!(matches!(result, Err(StompFrameError::JsonError(..))));
+    }
 }
@@ -11,7 +11,9 @@ use nom::multi::many0;
 use nom::sequence::{separated_pair, terminated};
 use nom::{AsChar, Finish, IResult, Parser};
 
-use crate::frame::{StompCommand, StompFrame, StompFrameError, CONTENT_LENGTH, HEADER_SEP};
+use crate::frame::{
+    decode_body, StompCommand, StompFrame, StompFrameError, CONTENT_LENGTH, HEADER_SEP,
+};
 
 macro_rules! stomp_command_parse_impl {
     ($typename: ident, $($command:ident),+) => {
@@ -22,6 +24,15 @@ macro_rules! stomp_command_parse_impl {
                     $(value(Self::$command, tag(stringify!($command)))),+
                 )).parse(input)
             }
+
+            /// Like [`Self::parse`], but yields [`nom::Err::Incomplete`] instead of a syntax
+            /// error when `input` is a prefix of a valid command name.
+            fn parse_streaming(input: &[u8]) -> IResult<&[u8], $typename> {
+                use nom::bytes::streaming::tag as tag_streaming;
+                alt((
+                    $(value(Self::$command, tag_streaming(stringify!($command)))),+
+                )).parse(input)
+            }
         }
     };
 }
@@ -32,20 +43,21 @@ impl TryFrom<&[u8]> for StompFrame {
     type Error = StompFrameError;
 
     fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
-        parse_frame(input).and_then(|(cmd, headers, body)| StompFrame::new(cmd, headers, body))
+        parse_frame(input)
+            .and_then(|(cmd, headers, body)| StompFrame::from_owned_body(cmd, headers, body))
     }
 }
 
 type StompHeaders = HashMap<String, String>;
 
-fn parse_frame(input: &[u8]) -> Result<(StompCommand, StompHeaders, &[u8]), StompFrameError> {
+fn parse_frame(input: &[u8]) -> Result<(StompCommand, StompHeaders, Vec<u8>), StompFrameError> {
     let (rest, (cmd, header_pairs)) = (
         terminated(StompCommand::parse, line_ending),
         terminated(many0(parse_header), line_ending),
     )
         .parse(input)
         .finish()?;
-    let headers = collect_headers(cmd, header_pairs)?;
+    let mut headers = collect_headers(cmd, header_pairs)?;
     let (_, body) = if let Some(content_len) = headers.get(CONTENT_LENGTH) {
         let Ok(body_len) = content_len.parse::<usize>() else {
             return Err(StompFrameError::HeaderError(
@@ -57,9 +69,94 @@ fn parse_frame(input: &[u8]) -> Result<(StompCommand, StompHeaders, &[u8]), Stom
     } else {
         parse_body(rest).finish()?
     };
+    let body = decode_body(&mut headers, body.to_vec())?;
     Ok((cmd, headers, body))
 }
 
+/// Outcome of attempting to parse one frame out of a streaming byte buffer.
+pub(super) enum FrameParse {
+    /// A full frame was parsed; the `usize` is the number of leading bytes of the input it
+    /// consumed, including the command line, headers, body and NUL terminator.
+    Frame(StompCommand, StompHeaders, Vec<u8>, usize),
+    /// `input` is a prefix of a valid frame; more bytes are needed before it can be parsed.
+    Incomplete,
+}
+
+/// Streaming counterpart of [`parse_frame`], for use by [`crate::frame::StompDecoder`]. Unlike
+/// `parse_frame`, a buffer that holds only part of a frame is reported as [`FrameParse::Incomplete`]
+/// rather than as a [`StompFrameError::SyntaxError`], so the caller can retry once more bytes
+/// have arrived. Trailing heartbeat newlines after the NUL terminator are left in `rest` for the
+/// caller to skip before the next frame.
+pub(super) fn parse_frame_streaming(input: &[u8]) -> Result<FrameParse, StompFrameError> {
+    use nom::character::streaming::line_ending;
+
+    let (rest, (cmd, header_pairs)) = match (
+        terminated(StompCommand::parse_streaming, line_ending),
+        terminated(many0(parse_header_streaming), line_ending),
+    )
+        .parse(input)
+    {
+        Ok(ok) => ok,
+        Err(nom::Err::Incomplete(_)) => return Ok(FrameParse::Incomplete),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => return Err(err.into()),
+    };
+    let mut headers = collect_headers(cmd, header_pairs)?;
+    let body_result = if let Some(content_len) = headers.get(CONTENT_LENGTH) {
+        let Ok(body_len) = content_len.parse::<usize>() else {
+            return Err(StompFrameError::HeaderError(
+                CONTENT_LENGTH.into(),
+                content_len.clone(),
+            ));
+        };
+        parse_body_with_len_streaming(rest, body_len)
+    } else {
+        parse_body_streaming(rest)
+    };
+    let (rest, body) = match body_result {
+        Ok(ok) => ok,
+        Err(nom::Err::Incomplete(_)) => return Ok(FrameParse::Incomplete),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => return Err(err.into()),
+    };
+    let consumed = input.len() - rest.len();
+    let body = decode_body(&mut headers, body.to_vec())?;
+    Ok(FrameParse::Frame(cmd, headers, body, consumed))
+}
+
+fn parse_header_streaming(input: &[u8]) -> IResult<&[u8], (&[u8], &[u8])> {
+    use nom::bytes::streaming::{take_while, take_while1};
+    use nom::character::streaming::{char as ch, line_ending};
+    terminated(
+        separated_pair(
+            take_while1(is_header_octet),
+            ch(HEADER_SEP.as_char()),
+            take_while(is_header_octet),
+        ),
+        line_ending,
+    )
+    .parse(input)
+}
+
+fn parse_body_with_len_streaming(input: &[u8], body_len: usize) -> IResult<&[u8], &[u8]> {
+    use nom::bytes::streaming::take;
+    use nom::character::streaming::char as ch;
+    terminated(take(body_len), ch('\0')).parse(input)
+}
+
+fn parse_body_streaming(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    use nom::bytes::streaming::take_while;
+    use nom::character::streaming::char as ch;
+    terminated(take_while(|c| c != b'\0'), ch('\0')).parse(input)
+}
+
+/// Skips any number of lone heartbeat newlines (STOMP 1.2 §2.1) at the start of `input`, e.g.
+/// between two frames or before the very first one. Returns the number of bytes skipped.
+pub(super) fn skip_heartbeats(input: &[u8]) -> IResult<&[u8], usize> {
+    use nom::character::streaming::line_ending;
+    let (rest, matched) = many0(line_ending).parse(input)?;
+    let consumed = matched.iter().map(|eol: &&[u8]| eol.len()).sum();
+    Ok((rest, consumed))
+}
+
 fn parse_header(input: &[u8]) -> IResult<&[u8], (&[u8], &[u8])> {
     terminated(
         separated_pair(
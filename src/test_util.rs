@@ -0,0 +1,298 @@
+#![cfg(feature = "test-util")]
+
+//! In-memory mock STOMP broker for integration tests.
+//!
+//! [`MockBroker`] implements just enough of the STOMP 1.2 protocol, over the crate's own
+//! [`StompDecoder`]/[`StompFrame`] codec, to drive a client's subscribe/publish flow end-to-end
+//! without a real socket: a [`DuplexStream`] pair stands in for the WebSocket transport.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::frame::{StompCommand, StompDecoder, StompFrame};
+
+const ID: &str = "id";
+const DESTINATION: &str = "destination";
+const SUBSCRIPTION: &str = "subscription";
+const MESSAGE_ID: &str = "message-id";
+const CONTENT_TYPE: &str = "content-type";
+const RECEIPT: &str = "receipt";
+const RECEIPT_ID: &str = "receipt-id";
+
+/// One end of an in-process duplex byte channel, standing in for a WebSocket connection.
+pub struct DuplexStream {
+    tx: Sender<Vec<u8>>,
+    rx: Receiver<Vec<u8>>,
+}
+
+impl DuplexStream {
+    fn pair() -> (Self, Self) {
+        let (tx_a, rx_a) = mpsc::channel();
+        let (tx_b, rx_b) = mpsc::channel();
+        (Self { tx: tx_a, rx: rx_b }, Self { tx: tx_b, rx: rx_a })
+    }
+
+    /// Sends a chunk of bytes to the other end of the channel.
+    pub fn send(&self, chunk: impl Into<Vec<u8>>) {
+        let _ = self.tx.send(chunk.into());
+    }
+
+    /// Blocks until the next chunk of bytes arrives from the other end, or returns `None` once
+    /// it has hung up.
+    pub fn recv(&self) -> Option<Vec<u8>> {
+        self.rx.recv().ok()
+    }
+}
+
+/// A minimal in-memory STOMP broker for exercising a client's subscribe/publish flow.
+///
+/// It accepts `CONNECT` and replies `CONNECTED`, tracks `SUBSCRIBE`/`UNSUBSCRIBE` by destination
+/// and subscription id, routes `SEND` frames to matching subscriptions as `MESSAGE` frames with
+/// generated `message-id`/`subscription` headers, and honors `receipt` headers by emitting
+/// `RECEIPT` frames.
+#[derive(Debug, Default)]
+pub struct MockBroker {
+    subscriptions: HashMap<String, String>, // subscription id -> destination
+    next_message_id: u64,
+}
+
+impl MockBroker {
+    /// Creates a broker with no subscriptions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the broker on a background thread and returns the client-facing end of the duplex
+    /// channel it listens on. The broker stops once the client disconnects or hangs up.
+    pub fn connect(self) -> DuplexStream {
+        let (client, broker_end) = DuplexStream::pair();
+        thread::spawn(move || self.run(broker_end));
+        client
+    }
+
+    fn run(mut self, stream: DuplexStream) {
+        let mut decoder = StompDecoder::new();
+        while let Some(chunk) = stream.recv() {
+            let Ok(frames) = decoder.decode(&chunk) else {
+                break;
+            };
+            for frame in frames {
+                let disconnecting = frame.cmd == StompCommand::DISCONNECT;
+                for response in self.handle_frame(&frame) {
+                    stream.send(
+                        Vec::try_from(&response)
+                            .expect("broker responses never set an unsupported content-encoding"),
+                    );
+                }
+                if disconnecting {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn handle_frame(&mut self, frame: &StompFrame) -> Vec<StompFrame> {
+        let responses = match frame.cmd {
+            StompCommand::CONNECT | StompCommand::STOMP => {
+                vec![StompFrame::new(StompCommand::CONNECTED, HashMap::new(), b"").unwrap()]
+            }
+            StompCommand::SUBSCRIBE => {
+                if let (Some(id), Some(destination)) =
+                    (frame.headers.get(ID), frame.headers.get(DESTINATION))
+                {
+                    self.subscriptions.insert(id.clone(), destination.clone());
+                }
+                Vec::new()
+            }
+            StompCommand::UNSUBSCRIBE => {
+                if let Some(id) = frame.headers.get(ID) {
+                    self.subscriptions.remove(id);
+                }
+                Vec::new()
+            }
+            StompCommand::SEND => self.route_send(frame),
+            _ => Vec::new(),
+        };
+        self.append_receipt(frame, responses)
+    }
+
+    fn route_send(&mut self, frame: &StompFrame) -> Vec<StompFrame> {
+        let Some(destination) = frame.headers.get(DESTINATION) else {
+            return Vec::new();
+        };
+        let matching: Vec<(String, String)> = self
+            .subscriptions
+            .iter()
+            .filter(|(_, dest)| *dest == destination)
+            .map(|(id, dest)| (id.clone(), dest.clone()))
+            .collect();
+        matching
+            .into_iter()
+            .map(|(sub_id, dest)| {
+                self.next_message_id += 1;
+                let mut headers = HashMap::from([
+                    (DESTINATION.to_string(), dest),
+                    (SUBSCRIPTION.to_string(), sub_id),
+                    (MESSAGE_ID.to_string(), format!("msg-{}", self.next_message_id)),
+                ]);
+                if let Some(content_type) = frame.headers.get(CONTENT_TYPE) {
+                    headers.insert(CONTENT_TYPE.to_string(), content_type.clone());
+                }
+                StompFrame::new(
+                    StompCommand::MESSAGE,
+                    headers,
+                    frame.body.clone().unwrap_or_default(),
+                )
+                .expect("a destination and an optional body make a valid MESSAGE frame")
+            })
+            .collect()
+    }
+
+    fn append_receipt(&self, frame: &StompFrame, mut responses: Vec<StompFrame>) -> Vec<StompFrame> {
+        if let Some(receipt_id) = frame.headers.get(RECEIPT) {
+            let receipt = StompFrame::new(
+                StompCommand::RECEIPT,
+                HashMap::from([(RECEIPT_ID.to_string(), receipt_id.clone())]),
+                b"",
+            )
+            .expect("RECEIPT frames never carry a body");
+            responses.push(receipt);
+        }
+        responses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recv_frame(stream: &DuplexStream) -> StompFrame {
+        let chunk = stream.recv().expect("broker is still connected");
+        let mut decoder = StompDecoder::new();
+        decoder
+            .decode(&chunk)
+            .unwrap()
+            .into_iter()
+            .next()
+            .expect("chunk contains one frame")
+    }
+
+    fn send_frame(stream: &DuplexStream, frame: &StompFrame) {
+        stream.send(Vec::try_from(frame).unwrap());
+    }
+
+    #[test]
+    fn connect_returns_connected() {
+        let client = MockBroker::new().connect();
+        send_frame(
+            &client,
+            &StompFrame::new(StompCommand::CONNECT, HashMap::new(), b"").unwrap(),
+        );
+        assert_eq!(recv_frame(&client).cmd, StompCommand::CONNECTED);
+    }
+
+    #[test]
+    fn send_is_routed_to_matching_subscription() {
+        let client = MockBroker::new().connect();
+        send_frame(
+            &client,
+            &StompFrame::new(StompCommand::CONNECT, HashMap::new(), b"").unwrap(),
+        );
+        assert_eq!(recv_frame(&client).cmd, StompCommand::CONNECTED);
+
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::SUBSCRIBE,
+                HashMap::from([
+                    (ID.to_string(), "sub-0".to_string()),
+                    (DESTINATION.to_string(), "/queue/test".to_string()),
+                ]),
+                b"",
+            )
+            .unwrap(),
+        );
+
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::SEND,
+                HashMap::from([(DESTINATION.to_string(), "/queue/test".to_string())]),
+                b"hello",
+            )
+            .unwrap(),
+        );
+
+        let message = recv_frame(&client);
+        assert_eq!(message.cmd, StompCommand::MESSAGE);
+        assert_eq!(
+            message.headers.get(SUBSCRIPTION),
+            Some(&"sub-0".to_string())
+        );
+        assert_eq!(message.body, Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn send_with_receipt_emits_receipt_frame() {
+        let client = MockBroker::new().connect();
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::SEND,
+                HashMap::from([
+                    (DESTINATION.to_string(), "/queue/test".to_string()),
+                    (RECEIPT.to_string(), "r-0".to_string()),
+                ]),
+                b"",
+            )
+            .unwrap(),
+        );
+
+        let receipt = recv_frame(&client);
+        assert_eq!(receipt.cmd, StompCommand::RECEIPT);
+        assert_eq!(receipt.headers.get(RECEIPT_ID), Some(&"r-0".to_string()));
+    }
+
+    #[test]
+    fn unsubscribe_stops_routing_to_that_subscription() {
+        let client = MockBroker::new().connect();
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::SUBSCRIBE,
+                HashMap::from([
+                    (ID.to_string(), "sub-0".to_string()),
+                    (DESTINATION.to_string(), "/queue/test".to_string()),
+                ]),
+                b"",
+            )
+            .unwrap(),
+        );
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::UNSUBSCRIBE,
+                HashMap::from([(ID.to_string(), "sub-0".to_string())]),
+                b"",
+            )
+            .unwrap(),
+        );
+        send_frame(
+            &client,
+            &StompFrame::new(
+                StompCommand::SEND,
+                HashMap::from([
+                    (DESTINATION.to_string(), "/queue/test".to_string()),
+                    (RECEIPT.to_string(), "r-0".to_string()),
+                ]),
+                b"",
+            )
+            .unwrap(),
+        );
+
+        // Only the RECEIPT for the SEND should arrive; the (removed) subscription gets no MESSAGE.
+        let response = recv_frame(&client);
+        assert_eq!(response.cmd, StompCommand::RECEIPT);
+    }
+}
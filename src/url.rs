@@ -3,10 +3,11 @@ use std::fmt;
 use thiserror::Error;
 use url::{ParseError, Url};
 
-/// URL for a secure STOMP-over-WebSocket connection.
+/// URL for a STOMP-over-WebSocket connection.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StompUrl {
     url: Url,
+    secure: bool,
 }
 
 impl StompUrl {
@@ -24,23 +25,69 @@ impl StompUrl {
     /// # Errors
     ///
     /// The [`StompUrlError`] will be returned when the URL:
-    /// - uses a scheme other than `wss`,
+    /// - uses the plaintext `ws` scheme (use [`Self::new_insecure`] to opt into that),
+    /// - uses a scheme other than `ws`/`wss`,
     /// - has a fragment (`wss://example.com/#fragment` is not a valid WebSocket address, see
     ///   [MDN](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/WebSocket#exceptions)),
     /// - contains syntax errors.
     pub fn new(url: impl AsRef<str>) -> Result<Self, StompUrlError> {
+        let url = Self::parse(url)?;
+        match url.scheme() {
+            "wss" => Ok(Self { url, secure: true }),
+            "ws" => Err(StompUrlError::InsecureNotAllowed),
+            _ => Err(StompUrlError::InvalidScheme),
+        }
+    }
+
+    /// Parse a WebSocket URL from a string, also accepting the plaintext `ws` scheme.
+    ///
+    /// This is meant for local development against a plaintext broker (e.g. a dev RabbitMQ or a
+    /// mock server on `127.0.0.1`); production code should prefer [`Self::new`] and check
+    /// [`Self::is_secure`] before relying on a connection carrying credentials.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use leptos_stomp::StompUrl;
+    ///
+    /// let result = StompUrl::new_insecure("ws://localhost:61614").unwrap();
+    /// assert!(!result.is_secure());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The [`StompUrlError`] will be returned when the URL:
+    /// - uses a scheme other than `ws` or `wss`,
+    /// - has a fragment,
+    /// - contains syntax errors.
+    pub fn new_insecure(url: impl AsRef<str>) -> Result<Self, StompUrlError> {
+        let url = Self::parse(url)?;
+        match url.scheme() {
+            "wss" => Ok(Self { url, secure: true }),
+            "ws" => Ok(Self {
+                url,
+                secure: false,
+            }),
+            _ => Err(StompUrlError::InvalidScheme),
+        }
+    }
+
+    fn parse(url: impl AsRef<str>) -> Result<Url, StompUrlError> {
         let url = Url::parse(url.as_ref())?;
-        if url.scheme() != "wss" {
-            Err(StompUrlError::InvalidScheme)
-        } else if url.fragment().is_some() {
+        if url.fragment().is_some() {
             Err(StompUrlError::HasFragment)
         } else {
-            Ok(Self { url })
+            Ok(url)
         }
     }
+
+    /// Returns `true` if this URL uses the encrypted `wss` scheme.
+    pub fn is_secure(&self) -> bool {
+        self.secure
+    }
 }
 
-/// An error which can be returned by [`StompUrl::new`].
+/// An error which can be returned by [`StompUrl::new`] or [`StompUrl::new_insecure`].
 ///
 /// # Examples
 ///
@@ -50,6 +97,9 @@ impl StompUrl {
 /// let scheme_err = StompUrl::new("http://example.com"); // URL doesn't use the WSS scheme
 /// assert_eq!(scheme_err, Err(StompUrlError::InvalidScheme));
 ///
+/// let insecure_err = StompUrl::new("ws://example.com"); // URL uses the plaintext WS scheme
+/// assert_eq!(insecure_err, Err(StompUrlError::InsecureNotAllowed));
+///
 /// let fragment_err = StompUrl::new("wss://example.com/#fragment"); // URL contains a fragment
 /// assert_eq!(fragment_err, Err(StompUrlError::HasFragment));
 ///
@@ -60,8 +110,10 @@ impl StompUrl {
 pub enum StompUrlError {
     #[error("invalid URL: {0}")]
     InvalidUrl(#[from] ParseError),
-    #[error("URL must use the WSS scheme")]
+    #[error("URL must use the WS or WSS scheme")]
     InvalidScheme,
+    #[error("URL uses the plaintext WS scheme; call StompUrl::new_insecure to allow this")]
+    InsecureNotAllowed,
     #[error("URL cannot contain a fragment")]
     HasFragment,
 }